@@ -0,0 +1,270 @@
+use crate::cli::{Cli, Exclude, OutputFormat};
+use crate::exec;
+use crate::format::{self, FileEntry};
+use crate::git_status;
+use crate::pack::{self, Candidate};
+use crate::query;
+use crate::types_filter;
+use crate::watch;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Custom `type -> globs` mappings layered on top of the defaults. This always
+/// returns an empty table, so only the `ignore` crate's built-in registry is
+/// used; no mappings are read from the configuration file.
+fn custom_types() -> HashMap<String, Vec<String>> {
+    HashMap::new()
+}
+
+/// Honor `--type-list` by printing the type table and signalling an early exit,
+/// mirroring the `--config-path` pattern.
+pub fn maybe_type_list(cli: &Cli) -> bool {
+    if cli.type_list {
+        types_filter::print_type_list(&custom_types());
+        return true;
+    }
+    false
+}
+
+/// A file after walking + tokenizing — the input to the post-walk pipeline that
+/// the new flags (`--query`, `--max-tokens`, structured formats, `--git`,
+/// `--exec`) operate on.
+#[derive(Debug, Clone)]
+pub struct WalkedFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub token_count: usize,
+    pub content: String,
+}
+
+/// Per-path annotations rendered in the tree output.
+#[derive(Default, Clone)]
+struct Annotation {
+    omitted_tokens: Option<usize>,
+    score: Option<f64>,
+    git: Option<char>,
+}
+
+/// Keep the process alive and re-`emit` whenever a relevant file under the
+/// analyzed paths changes. Changes to hidden paths (unless `--hidden`) and to
+/// files whose name ends with an `--exclude` pattern don't trigger rebuilds;
+/// `.gitignore` rules are not consulted.
+pub fn run_watch<F>(cli: &Cli, emit: F) -> Result<()>
+where
+    F: FnMut() -> Result<()>,
+{
+    watch::watch(&cli.paths, |path| is_relevant(cli, path), emit)
+}
+
+/// Whether a changed path should trigger a rebuild: false when any component
+/// starts with `.` and `--hidden` is off, or when the file name ends with an
+/// `--exclude` pattern with its leading `*`s removed.
+fn is_relevant(cli: &Cli, path: &Path) -> bool {
+    if !cli.hidden
+        && path
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+    {
+        return false;
+    }
+    if let Some(excludes) = &cli.exclude {
+        let name = path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+        for exclude in excludes {
+            if let Exclude::Pattern(pattern) = exclude {
+                let stem = pattern.trim_start_matches('*');
+                if name.ends_with(stem) {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Run the post-walk pipeline and return the text to emit.
+pub fn process(cli: &Cli, files: Vec<WalkedFile>) -> Result<String> {
+    let mut included = files;
+
+    // Named file-type filtering, consulted alongside the gitignore logic.
+    if cli.r#type.is_some() || cli.type_not.is_some() {
+        let types = types_filter::build_types(
+            cli.r#type.as_deref().unwrap_or(&[]),
+            cli.type_not.as_deref().unwrap_or(&[]),
+            &custom_types(),
+        )?;
+        included.retain(|f| !types.matched(&f.path, false).is_ignore());
+    }
+
+    // Query-driven relevance ranking: sort by descending TF-IDF score, prefix
+    // scores in the tree, and optionally drop zero-score files.
+    let mut scores: HashMap<PathBuf, f64> = HashMap::new();
+    if let Some(terms) = &cli.query {
+        let docs: Vec<String> = included.iter().map(|f| f.content.clone()).collect();
+        let doc_scores = query::score(terms, &docs);
+        for (file, s) in included.iter().zip(&doc_scores) {
+            scores.insert(file.path.clone(), *s);
+        }
+        let order = query::rank(&doc_scores, cli.query_filter);
+        let ranked = included.clone();
+        included = order.into_iter().map(|i| ranked[i].clone()).collect();
+    }
+
+    let mut omitted: Vec<Candidate> = Vec::new();
+    let mut summary: Option<String> = None;
+
+    // Token-budget packing: keep the files that fit, remember the rest so the
+    // tree can still show them annotated.
+    if let Some(budget) = cli.max_tokens {
+        let candidates = included
+            .iter()
+            .map(|f| Candidate {
+                path: f.path.clone(),
+                tokens: f.token_count,
+            })
+            .collect();
+        let result = pack::pack(candidates, budget, &cli.pack_order);
+        let keep: HashSet<PathBuf> = result.included.iter().map(|c| c.path.clone()).collect();
+        summary = Some(pack::summary_line(&result));
+        omitted = result.omitted;
+        included.retain(|f| keep.contains(&f.path));
+    }
+
+    // Per-file command execution turns the selected set into a pipeline stage
+    // and short-circuits the normal rendered output.
+    let selected: Vec<PathBuf> = included.iter().map(|f| f.path.clone()).collect();
+    if let Some(cmd) = &cli.exec_batch {
+        return exec::exec_batch(cmd, &selected);
+    }
+    if let Some(cmd) = &cli.exec {
+        return Ok(exec::exec_each(cmd, &selected, cli.print).join("\n"));
+    }
+
+    // Git working-tree status for the `--git` column.
+    let git = if cli.git {
+        cli.paths
+            .first()
+            .and_then(|root| git_status::status_map(root).ok())
+            .unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
+
+    let entries = file_entries(cli, &included);
+    let tree = || render_tree(cli.git, &included, &omitted, &scores, &git);
+    let files = || render_files(&entries);
+
+    let mut output = match cli.output.as_ref().unwrap_or(&OutputFormat::Both) {
+        OutputFormat::Tree => tree(),
+        OutputFormat::Files => files(),
+        OutputFormat::Both => format!("{}\n{}", tree(), files()),
+        OutputFormat::Json => format::render_json(&entries),
+        OutputFormat::Markdown => format::render_markdown(&entries),
+        OutputFormat::Xml => format::render_xml(&entries),
+    };
+
+    if let Some(summary) = summary {
+        output.push('\n');
+        output.push_str(&summary);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Build the structured-output entries, dropping token counts under `--no-tokens`.
+fn file_entries(cli: &Cli, files: &[WalkedFile]) -> Vec<FileEntry> {
+    files
+        .iter()
+        .map(|f| FileEntry {
+            path: f.path.clone(),
+            size: f.size,
+            token_count: (!cli.no_tokens).then_some(f.token_count),
+            content: f.content.clone(),
+        })
+        .collect()
+}
+
+/// Concatenate file contents under a path header, the plain `Files` output.
+fn render_files(entries: &[FileEntry]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&format!("===> {} <===\n", entry.path.display()));
+        out.push_str(&entry.content);
+        if !entry.content.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Render the directory tree. Budget-omitted files are annotated with
+/// `[omitted: N tok]`, query scores are prefixed, and when `show_git` is set a
+/// status column is rendered to the left of the tree, with directories rolling
+/// up the most significant status of their descendants.
+fn render_tree(
+    show_git: bool,
+    included: &[WalkedFile],
+    omitted: &[Candidate],
+    scores: &HashMap<PathBuf, f64>,
+    git: &HashMap<PathBuf, char>,
+) -> String {
+    let mut leaves: BTreeMap<PathBuf, Annotation> = BTreeMap::new();
+    for file in included {
+        let annotation = leaves.entry(file.path.clone()).or_default();
+        annotation.score = scores.get(&file.path).copied();
+        annotation.git = git.get(&file.path).copied();
+    }
+    for candidate in omitted {
+        let annotation = leaves.entry(candidate.path.clone()).or_default();
+        annotation.omitted_tokens = Some(candidate.tokens);
+        annotation.git = git.get(&candidate.path).copied();
+    }
+
+    // Every node (files and their ancestor directories), with a status glyph.
+    let mut glyphs: BTreeMap<PathBuf, char> = BTreeMap::new();
+    let mut descendants: HashMap<PathBuf, Vec<char>> = HashMap::new();
+    for (path, annotation) in &leaves {
+        let glyph = annotation.git.unwrap_or(' ');
+        glyphs.insert(path.clone(), glyph);
+        let mut ancestor = path.parent();
+        while let Some(dir) = ancestor {
+            if dir.as_os_str().is_empty() {
+                break;
+            }
+            descendants.entry(dir.to_path_buf()).or_default().push(glyph);
+            glyphs.entry(dir.to_path_buf()).or_insert(' ');
+            ancestor = dir.parent();
+        }
+    }
+    for (dir, glyphs_below) in &descendants {
+        glyphs.insert(dir.clone(), git_status::rollup(glyphs_below.iter()));
+    }
+
+    let mut out = String::new();
+    for (path, glyph) in &glyphs {
+        if show_git {
+            out.push(*glyph);
+            out.push(' ');
+        }
+        let depth = path.components().count().saturating_sub(1);
+        out.push_str(&"  ".repeat(depth));
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        if let Some(annotation) = leaves.get(path) {
+            if let Some(score) = annotation.score {
+                out.push_str(&format!("[{score:.3}] "));
+            }
+            out.push_str(&name);
+            if let Some(tokens) = annotation.omitted_tokens {
+                out.push(' ');
+                out.push_str(&pack::omitted_annotation(tokens));
+            }
+        } else {
+            out.push_str(&name);
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -8,6 +8,16 @@ pub enum OutputFormat {
     Tree,
     Files,
     Both,
+    Json,
+    Markdown,
+    Xml,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum PackOrder {
+    Smallest,
+    Largest,
+    Path,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -47,6 +57,18 @@ pub struct Cli {
     #[arg(short, long, value_parser = parse_exclude, value_delimiter = ',')]
     pub exclude: Option<Vec<Exclude>>,
 
+    /// Only include files of the given types (e.g. "rust,go")
+    #[arg(short = 't', long = "type", value_delimiter = ',')]
+    pub r#type: Option<Vec<String>>,
+
+    /// Exclude files of the given types (e.g. "markdown")
+    #[arg(short = 'T', long = "type-not", value_delimiter = ',')]
+    pub type_not: Option<Vec<String>>,
+
+    /// Print the known type -> glob table and exit
+    #[arg(long)]
+    pub type_list: bool,
+
     /// Maximum file size in bytes
     #[arg(short, long)]
     pub max_size: Option<u64>,
@@ -83,6 +105,22 @@ pub struct Cli {
     #[arg(long)]
     pub no_tokens: bool,
 
+    /// Rank files by TF-IDF relevance to the given query terms
+    #[arg(long)]
+    pub query: Option<String>,
+
+    /// Drop files that score zero against --query
+    #[arg(long)]
+    pub query_filter: bool,
+
+    /// Hard token budget for the emitted content; files are greedily packed until it is exhausted
+    #[arg(long)]
+    pub max_tokens: Option<usize>,
+
+    /// Order in which files are considered when packing against --max-tokens
+    #[arg(long, value_enum, default_value = "smallest")]
+    pub pack_order: PackOrder,
+
     /// Tokenizer to use (tiktoken or huggingface)
     #[arg(long, value_enum)]
     pub tokenizer: Option<TokenizerType>,
@@ -99,9 +137,25 @@ pub struct Cli {
     #[arg(long)]
     pub interactive: bool,
 
+    /// Annotate tree entries with their git working-tree status
+    #[arg(long)]
+    pub git: bool,
+
+    /// Watch the analyzed paths and regenerate output on filesystem changes
+    #[arg(short = 'w', long)]
+    pub watch: bool,
+
     /// Output as Pdf
     #[arg(long)]
     pub pdf: Option<PathBuf>,
+
+    /// Run a command for each selected file ({}, {/}, {.}, {//} placeholders)
+    #[arg(long)]
+    pub exec: Option<String>,
+
+    /// Run a single command with all matched paths as arguments
+    #[arg(long)]
+    pub exec_batch: Option<String>,
 }
 
 impl Cli {
@@ -0,0 +1,142 @@
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Substitute fd-style placeholders in a single command token for `path`:
+/// `{}` full path, `{/}` basename, `{.}` path without extension, `{//}` parent
+/// directory. A token with no placeholder is returned unchanged.
+fn substitute_token(token: &str, path: &Path) -> String {
+    let full = path.to_string_lossy();
+    let basename = path
+        .file_name()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    let parent = path
+        .parent()
+        .map(|p| p.to_string_lossy())
+        .unwrap_or_default();
+    let no_ext = path
+        .with_extension("")
+        .to_string_lossy()
+        .into_owned();
+
+    token
+        .replace("{//}", &parent)
+        .replace("{/}", &basename)
+        .replace("{.}", &no_ext)
+        .replace("{}", &full)
+}
+
+/// Split a command template into tokens, substituting placeholders for `path`.
+/// When the template contains no placeholder, the path is appended as a final
+/// argument (matching fd's default behavior).
+pub fn build_args(template: &str, path: &Path) -> Vec<String> {
+    let has_placeholder = ["{}", "{/}", "{.}", "{//}"]
+        .iter()
+        .any(|p| template.contains(p));
+    let mut args: Vec<String> = split_command(template)
+        .iter()
+        .map(|token| substitute_token(token, path))
+        .collect();
+    if !has_placeholder {
+        args.push(path.to_string_lossy().into_owned());
+    }
+    args
+}
+
+/// Split a command template into argv tokens, honoring single and double quotes
+/// so a quoted argument containing spaces stays a single token
+/// (e.g. `sh -c 'fmt {}'` yields three tokens, not four).
+pub fn split_command(template: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut started = false;
+    for ch in template.chars() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '\'' || ch == '"' => {
+                quote = Some(ch);
+                started = true;
+            }
+            None if ch.is_whitespace() => {
+                if started {
+                    tokens.push(std::mem::take(&mut current));
+                    started = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                started = true;
+            }
+        }
+    }
+    if started {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Run `template` once per path on the Rayon pool. When `print` is set, each
+/// invocation's stdout is returned under a `==> path <==` header.
+pub fn exec_each(template: &str, paths: &[PathBuf], print: bool) -> Vec<String> {
+    paths
+        .par_iter()
+        .map(|path| match run(&build_args(template, path)) {
+            Ok(stdout) if print => format!("==> {} <==\n{}", path.display(), stdout),
+            Ok(stdout) => stdout,
+            Err(err) => format!("==> {} <==\nerror: {err}", path.display()),
+        })
+        .collect()
+}
+
+/// Run `template` once with every matched path appended as a single argument
+/// list.
+pub fn exec_batch(template: &str, paths: &[PathBuf]) -> Result<String> {
+    let mut args = split_command(template);
+    args.extend(paths.iter().map(|p| p.to_string_lossy().into_owned()));
+    run(&args)
+}
+
+fn run(args: &[String]) -> Result<String> {
+    let (program, rest) = args.split_first().context("empty command")?;
+    let output = Command::new(program)
+        .args(rest)
+        .output()
+        .with_context(|| format!("failed to run `{program}`"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_substituted() {
+        let path = Path::new("src/foo/bar.rs");
+        let args = build_args("wc -l {}", path);
+        assert_eq!(args, vec!["wc", "-l", "src/foo/bar.rs"]);
+    }
+
+    #[test]
+    fn basename_parent_and_stem_placeholders() {
+        let path = Path::new("src/foo/bar.rs");
+        assert_eq!(substitute_token("{/}", path), "bar.rs");
+        assert_eq!(substitute_token("{//}", path), "src/foo");
+        assert_eq!(substitute_token("{.}", path), "src/foo/bar");
+    }
+
+    #[test]
+    fn path_is_appended_when_no_placeholder() {
+        let args = build_args("cat", Path::new("a.rs"));
+        assert_eq!(args, vec!["cat", "a.rs"]);
+    }
+
+    #[test]
+    fn quoted_argument_with_spaces_stays_one_token() {
+        let args = build_args("sh -c 'fmt {}'", Path::new("a.rs"));
+        assert_eq!(args, vec!["sh", "-c", "fmt a.rs"]);
+    }
+}
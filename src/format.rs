@@ -0,0 +1,133 @@
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// A single file rendered into a structured output document.
+#[derive(Debug, Clone)]
+pub struct FileEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    /// `None` when `--no-tokens` is set.
+    pub token_count: Option<usize>,
+    pub content: String,
+}
+
+/// Map a file extension to a fenced-code-block / syntax language hint.
+fn language_hint(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("rs") => "rust",
+        Some("go") => "go",
+        Some("py") => "python",
+        Some("js") => "javascript",
+        Some("ts") => "typescript",
+        Some("c" | "h") => "c",
+        Some("cpp" | "cc" | "hpp") => "cpp",
+        Some("md") => "markdown",
+        Some("json") => "json",
+        Some("toml") => "toml",
+        Some("yaml" | "yml") => "yaml",
+        Some("sh") => "bash",
+        _ => "",
+    }
+}
+
+/// Emit a machine-readable JSON document: the directory tree plus an array of
+/// file objects. Token counts are omitted when `--no-tokens` dropped them.
+pub fn render_json(files: &[FileEntry]) -> String {
+    let objects: Vec<Value> = files
+        .iter()
+        .map(|f| {
+            let mut obj = json!({
+                "path": f.path,
+                "size": f.size,
+                "content": f.content,
+            });
+            if let Some(tokens) = f.token_count {
+                obj["token_count"] = json!(tokens);
+            }
+            obj
+        })
+        .collect();
+
+    let document = json!({
+        "tree": build_tree(files),
+        "files": objects,
+    });
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
+
+/// Build a nested directory tree object from the flat file list.
+fn build_tree(files: &[FileEntry]) -> Value {
+    let mut root = Map::new();
+    for file in files {
+        let components: Vec<_> = file.path.iter().filter_map(|c| c.to_str()).collect();
+        insert_path(&mut root, &components);
+    }
+    Value::Object(root)
+}
+
+/// Insert a path's components into the tree, promoting a colliding leaf to a
+/// directory when another path descends beneath it (e.g. both `a` and `a/b`).
+fn insert_path(map: &mut Map<String, Value>, components: &[&str]) {
+    let Some((head, tail)) = components.split_first() else {
+        return;
+    };
+    if tail.is_empty() {
+        map.entry((*head).to_string()).or_insert(Value::Null);
+        return;
+    }
+    let child = map
+        .entry((*head).to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !child.is_object() {
+        *child = Value::Object(Map::new());
+    }
+    insert_path(child.as_object_mut().expect("promoted to object"), tail);
+}
+
+/// Wrap each file in a fenced code block with a language hint and a heading.
+pub fn render_markdown(files: &[FileEntry]) -> String {
+    let mut out = String::new();
+    for file in files {
+        out.push_str(&format!("## {}\n\n", file.path.display()));
+        if let Some(tokens) = file.token_count {
+            out.push_str(&format!("_{tokens} tokens_\n\n"));
+        }
+        out.push_str(&format!("```{}\n", language_hint(&file.path)));
+        out.push_str(&file.content);
+        if !file.content.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str("```\n\n");
+    }
+    out
+}
+
+/// Emit Claude-style `<file path="…">…</file>` blocks inside a root element.
+pub fn render_xml(files: &[FileEntry]) -> String {
+    let mut out = String::from("<files>\n");
+    for file in files {
+        let tokens = file
+            .token_count
+            .map(|t| format!(" token_count=\"{t}\""))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "  <file path=\"{}\"{}>\n",
+            xml_escape(&file.path.display().to_string()),
+            tokens,
+        ));
+        out.push_str(&xml_escape(&file.content));
+        if !file.content.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str("  </file>\n");
+    }
+    out.push_str("</files>\n");
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
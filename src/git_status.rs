@@ -0,0 +1,70 @@
+use anyhow::Result;
+use git2::{Repository, Status, StatusOptions};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Collect a path -> status-glyph map for the repository enclosing `root`.
+///
+/// Glyphs follow the exa convention: staged (`+`), modified (`M`), untracked
+/// (`?`), renamed (`R`), ignored (`!`). Keys are repo-relative paths, matching
+/// how the walker reports files.
+pub fn status_map(root: &Path) -> Result<HashMap<PathBuf, char>> {
+    let repo = Repository::discover(root)?;
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(true)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true);
+
+    let mut map = HashMap::new();
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        if let Some(path) = entry.path() {
+            map.insert(PathBuf::from(path), glyph(entry.status()));
+        }
+    }
+    Ok(map)
+}
+
+/// Map a libgit2 status bitset to its most significant glyph.
+fn glyph(status: Status) -> char {
+    if status.contains(Status::INDEX_RENAMED) || status.contains(Status::WT_RENAMED) {
+        'R'
+    } else if status.intersects(
+        Status::INDEX_NEW | Status::INDEX_MODIFIED | Status::INDEX_DELETED | Status::INDEX_TYPECHANGE,
+    ) {
+        '+'
+    } else if status.intersects(Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_TYPECHANGE) {
+        'M'
+    } else if status.contains(Status::WT_NEW) {
+        '?'
+    } else if status.contains(Status::IGNORED) {
+        '!'
+    } else {
+        ' '
+    }
+}
+
+/// Significance ranking used to roll a directory's status up from its most
+/// significant descendant (higher wins).
+fn rank(glyph: char) -> u8 {
+    match glyph {
+        'R' => 5,
+        '+' => 4,
+        'M' => 3,
+        '?' => 2,
+        '!' => 1,
+        _ => 0,
+    }
+}
+
+/// Roll a directory's status up to the most significant status among the
+/// statuses of its descendants.
+pub fn rollup<'a>(descendants: impl IntoIterator<Item = &'a char>) -> char {
+    descendants
+        .into_iter()
+        .copied()
+        .max_by_key(|g| rank(*g))
+        .unwrap_or(' ')
+}
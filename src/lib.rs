@@ -0,0 +1,9 @@
+pub mod app;
+pub mod cli;
+pub mod exec;
+pub mod format;
+pub mod git_status;
+pub mod pack;
+pub mod query;
+pub mod types_filter;
+pub mod watch;
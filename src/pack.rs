@@ -0,0 +1,117 @@
+use crate::cli::PackOrder;
+use std::path::PathBuf;
+
+/// A walked file considered for inclusion, paired with its token count.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub path: PathBuf,
+    pub tokens: usize,
+}
+
+/// Outcome of packing candidates against a token budget.
+#[derive(Debug, Default)]
+pub struct PackResult {
+    pub included: Vec<Candidate>,
+    pub omitted: Vec<Candidate>,
+    pub total_tokens: usize,
+}
+
+/// Greedily pack `candidates` into a `max_tokens` budget.
+///
+/// Candidates are first ordered by `order`, then accumulated while the running
+/// token sum stays within budget. A file whose token count exceeds the budget
+/// still remaining is skipped; consequently any file larger than `max_tokens`
+/// on its own is never included.
+pub fn pack(mut candidates: Vec<Candidate>, max_tokens: usize, order: &PackOrder) -> PackResult {
+    match order {
+        PackOrder::Smallest => candidates.sort_by_key(|c| c.tokens),
+        PackOrder::Largest => candidates.sort_by(|a, b| b.tokens.cmp(&a.tokens)),
+        PackOrder::Path => candidates.sort_by(|a, b| a.path.cmp(&b.path)),
+    }
+
+    let mut result = PackResult::default();
+    for candidate in candidates {
+        let remaining = max_tokens - result.total_tokens;
+        if candidate.tokens <= remaining {
+            result.total_tokens += candidate.tokens;
+            result.included.push(candidate);
+        } else {
+            result.omitted.push(candidate);
+        }
+    }
+    result
+}
+
+/// The tree annotation shown next to a file dropped by the token budget.
+pub fn omitted_annotation(tokens: usize) -> String {
+    format!("[omitted: {tokens} tok]")
+}
+
+/// The final summary line reporting included/excluded counts and realized total.
+pub fn summary_line(result: &PackResult) -> String {
+    format!(
+        "packed {} files ({} omitted), {} tokens total",
+        result.included.len(),
+        result.omitted.len(),
+        result.total_tokens,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(path: &str, tokens: usize) -> Candidate {
+        Candidate {
+            path: PathBuf::from(path),
+            tokens,
+        }
+    }
+
+    #[test]
+    fn smallest_first_packs_many_small_files() {
+        let candidates = vec![
+            candidate("big.rs", 80),
+            candidate("a.rs", 10),
+            candidate("b.rs", 20),
+        ];
+        let result = pack(candidates, 50, &PackOrder::Smallest);
+        let included: Vec<_> = result.included.iter().map(|c| c.tokens).collect();
+        assert_eq!(included, vec![10, 20]);
+        assert_eq!(result.total_tokens, 30);
+        assert_eq!(result.omitted.len(), 1);
+    }
+
+    #[test]
+    fn file_larger_than_budget_is_never_included() {
+        let result = pack(vec![candidate("huge.rs", 500)], 100, &PackOrder::Smallest);
+        assert!(result.included.is_empty());
+        assert_eq!(result.omitted.len(), 1);
+        assert_eq!(result.total_tokens, 0);
+    }
+
+    #[test]
+    fn file_exceeding_remaining_budget_is_skipped() {
+        let candidates = vec![candidate("a.rs", 70), candidate("b.rs", 40)];
+        // largest first: 70 fits, 40 exceeds the remaining 30 and is skipped.
+        let result = pack(candidates, 100, &PackOrder::Largest);
+        assert_eq!(result.included.len(), 1);
+        assert_eq!(result.included[0].tokens, 70);
+        assert_eq!(result.omitted.len(), 1);
+    }
+
+    #[test]
+    fn path_order_is_deterministic() {
+        let candidates = vec![candidate("z.rs", 10), candidate("a.rs", 10)];
+        let result = pack(candidates, 100, &PackOrder::Path);
+        let paths: Vec<_> = result.included.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(paths, vec![PathBuf::from("a.rs"), PathBuf::from("z.rs")]);
+    }
+
+    #[test]
+    fn annotation_and_summary_render() {
+        assert_eq!(omitted_annotation(4200), "[omitted: 4200 tok]");
+        let result = pack(vec![candidate("a.rs", 10)], 100, &PackOrder::Smallest);
+        assert_eq!(summary_line(&result), "packed 1 files (0 omitted), 10 tokens total");
+    }
+}
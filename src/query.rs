@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+/// Split text into lowercased alphanumeric word tokens, the same way documents
+/// and the query are tokenized.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+/// Score each document against `query` using classic TF-IDF.
+///
+/// For every query term the contribution is `tf(term, doc) * ln(N / (1 + df))`,
+/// where `tf` is the raw term count in the document, `df` is the number of
+/// documents containing the term, and `N` is the corpus size. The idf factor is
+/// clamped at 0: `ln(N / (1 + df))` is zero for a term missing from exactly one
+/// document and negative for a term present in every document, and the clamp
+/// keeps such a term from lowering a document's score. A document that matches
+/// only zero-idf terms scores 0 and is dropped by `--query-filter`. Returns one
+/// score per document, aligned with `docs`. An empty corpus yields an empty
+/// vector.
+pub fn score(query: &str, docs: &[String]) -> Vec<f64> {
+    let n = docs.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let terms = tokenize(query);
+    let tokenized: Vec<Vec<String>> = docs.iter().map(|d| tokenize(d)).collect();
+
+    // Document frequency per term.
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for term in &terms {
+        let count = tokenized
+            .iter()
+            .filter(|doc| doc.iter().any(|t| t == term))
+            .count();
+        df.insert(term.as_str(), count);
+    }
+
+    tokenized
+        .iter()
+        .map(|doc| {
+            terms
+                .iter()
+                .map(|term| {
+                    let tf = doc.iter().filter(|t| *t == term).count() as f64;
+                    let idf = (n as f64 / (1.0 + df[term.as_str()] as f64)).ln().max(0.0);
+                    tf * idf
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Indices of `docs` sorted by descending score. When `filter` is set,
+/// zero-score documents are dropped.
+pub fn rank(scores: &[f64], filter: bool) -> Vec<usize> {
+    let mut ranked: Vec<usize> = (0..scores.len())
+        .filter(|&i| !filter || scores[i] > 0.0)
+        .collect();
+    ranked.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap_or(std::cmp::Ordering::Equal));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_on_non_alphanumeric_and_lowercases() {
+        assert_eq!(tokenize("Async-Retry  logic!"), vec!["async", "retry", "logic"]);
+    }
+
+    #[test]
+    fn empty_corpus_is_guarded() {
+        assert!(score("anything", &[]).is_empty());
+    }
+
+    #[test]
+    fn relevant_document_scores_higher() {
+        let docs = vec![
+            "async retry retry logic here".to_string(),
+            "totally unrelated prose".to_string(),
+            "more unrelated text".to_string(),
+        ];
+        let scores = score("retry", &docs);
+        assert!(scores[0] > scores[1]);
+    }
+
+    #[test]
+    fn filter_drops_zero_score_documents() {
+        let docs = vec!["retry".to_string(), "nothing".to_string(), "else".to_string()];
+        let scores = score("retry", &docs);
+        assert_eq!(rank(&scores, true), vec![0]);
+    }
+}
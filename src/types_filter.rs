@@ -0,0 +1,51 @@
+use anyhow::Result;
+use ignore::types::{Types, TypesBuilder};
+use std::collections::HashMap;
+
+/// Build a compiled [`Types`] matcher for the walker to consult alongside the
+/// gitignore logic.
+///
+/// The builder is seeded with `add_defaults()` (the same registry ripgrep/fd
+/// expose), then extended with any caller-supplied custom mappings
+/// (e.g. `{ "proto" = ["*.proto"] }`), and finally narrowed by the `--type` /
+/// `--type-not` selections.
+pub fn build_types(
+    selected: &[String],
+    negated: &[String],
+    custom: &HashMap<String, Vec<String>>,
+) -> Result<Types> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+
+    for (name, globs) in custom {
+        for glob in globs {
+            builder.add(name, glob)?;
+        }
+    }
+
+    for name in selected {
+        builder.select(name);
+    }
+    for name in negated {
+        builder.negate(name);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Print the known type -> glob table and return, mirroring `--config-path`.
+pub fn print_type_list(custom: &HashMap<String, Vec<String>>) {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    for (name, globs) in custom {
+        for glob in globs {
+            let _ = builder.add(name, glob);
+        }
+    }
+
+    let mut definitions = builder.definitions();
+    definitions.sort_by(|a, b| a.name().cmp(b.name()));
+    for def in definitions {
+        println!("{}: {}", def.name(), def.globs().join(", "));
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// Events arriving within this window are coalesced into a single rebuild.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watch `paths` recursively and invoke `rebuild` whenever a relevant file is
+/// created, modified, or deleted.
+///
+/// `is_relevant` decides which changed paths count; a burst whose paths it all
+/// rejects triggers no rebuild. Events that
+/// land within [`DEBOUNCE`] of one another are collapsed into one rebuild so a
+/// burst of saves only regenerates the output once.
+pub fn watch<F, G>(paths: &[PathBuf], is_relevant: G, mut rebuild: F) -> Result<()>
+where
+    F: FnMut() -> Result<()>,
+    G: Fn(&Path) -> bool,
+{
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })?;
+    for path in paths {
+        watcher.watch(path, RecursiveMode::Recursive)?;
+    }
+
+    // Emit once up front, then react to changes.
+    rebuild()?;
+
+    loop {
+        let Ok(first) = rx.recv() else { break };
+
+        // Collect the opening event and everything that lands within the
+        // debounce window, rebuilding only if *any* of them touched a relevant
+        // path — a burst confined to ignored files is dropped.
+        let mut relevant = touches_relevant(&first, &is_relevant);
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            relevant |= touches_relevant(&event, &is_relevant);
+        }
+        if relevant {
+            rebuild()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a watcher result refers to at least one relevant path.
+fn touches_relevant<G: Fn(&Path) -> bool>(
+    event: &notify::Result<notify::Event>,
+    is_relevant: &G,
+) -> bool {
+    matches!(event, Ok(e) if e.paths.iter().any(|p| is_relevant(p)))
+}